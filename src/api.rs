@@ -0,0 +1,161 @@
+// BetterGovPH Open Data Visualization - JSON aggregation API
+//
+// CouchDB-style "views" over each dataset. A view picks a grouping key
+// (`byAgency`, `byDateMonthYear`, `byRegion`) and optionally reduces the grouped
+// rows to a count or a sum of an amount field. Results are JSON arrays of
+// `{ key, value }` pairs ready for direct consumption by the chart frontend.
+
+use actix_web::{web, HttpResponse, Result, error::Error as ActixError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::data::{self, Dataset};
+
+// The grouping key a view emits.
+#[derive(Clone, Copy)]
+enum GroupBy {
+    Agency,
+    DateMonthYear,
+    Region,
+}
+
+// Query parameters shared by every view.
+#[derive(Deserialize)]
+pub struct ViewParams {
+    // `count` or `sum`; absent means return the grouped rows verbatim.
+    reduce: Option<String>,
+    // Amount field to sum when `reduce=sum` (defaults to the dataset's amount column).
+    field: Option<String>,
+}
+
+// One `{ key, value }` pair in a view response.
+#[derive(Serialize)]
+struct ViewRow {
+    key: String,
+    value: Value,
+}
+
+// Extract the grouping key for a record under the requested view.
+fn group_key(record: &data::Record, group: GroupBy) -> Option<String> {
+    match group {
+        GroupBy::Agency => data::field(record, data::AGENCY_FIELDS).map(str::to_string),
+        GroupBy::Region => data::field(record, data::REGION_FIELDS).map(str::to_string),
+        GroupBy::DateMonthYear => {
+            let raw = data::field(record, data::DATE_FIELDS)?;
+            truncate_to_month(raw)
+        }
+    }
+}
+
+// Truncate an ISO-ish date string to `YYYY-MM`, tolerating `YYYY-MM-DD`,
+// `YYYY/MM/DD` and `MM/DD/YYYY` shapes.
+fn truncate_to_month(raw: &str) -> Option<String> {
+    let normalized = raw.replace('/', "-");
+    let parts: Vec<&str> = normalized.split('-').collect();
+    match parts.as_slice() {
+        [year, month, ..] if year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()) => {
+            Some(format!("{year}-{month:0>2}"))
+        }
+        [month, _day, year] if year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()) => {
+            Some(format!("{year}-{month:0>2}"))
+        }
+        _ => None,
+    }
+}
+
+// Build the grouped view, applying the optional reduction.
+fn build_view(dataset: Dataset, group: GroupBy, params: &ViewParams) -> Vec<ViewRow> {
+    let records = data::load(dataset);
+
+    let mut grouped: BTreeMap<String, Vec<data::Record>> = BTreeMap::new();
+    for record in records {
+        if let Some(key) = group_key(&record, group) {
+            grouped.entry(key).or_default().push(record);
+        }
+    }
+
+    let amount_fields: Vec<&str> = match &params.field {
+        Some(field) => vec![field.as_str()],
+        None => data::AMOUNT_FIELDS.to_vec(),
+    };
+
+    grouped
+        .into_iter()
+        .map(|(key, rows)| {
+            let value = match params.reduce.as_deref() {
+                Some("count") => Value::from(rows.len()),
+                Some("sum") => {
+                    let sum: f64 = rows
+                        .iter()
+                        .filter_map(|r| data::field(r, &amount_fields))
+                        .filter_map(data::parse_amount)
+                        .sum();
+                    Value::from(sum)
+                }
+                _ => serde_json::to_value(&rows).unwrap_or(Value::Null),
+            };
+            ViewRow { key, value }
+        })
+        .collect()
+}
+
+// Resolve the dataset slug then render a view, or 404 for an unknown dataset.
+async fn render_view(
+    dataset: &str,
+    group: GroupBy,
+    params: ViewParams,
+) -> Result<HttpResponse, ActixError> {
+    let dataset = Dataset::from_slug(dataset)
+        .ok_or_else(|| actix_web::error::ErrorNotFound("unknown dataset"))?;
+    let rows = build_view(dataset, group, &params);
+    Ok(HttpResponse::Ok().json(rows))
+}
+
+async fn by_agency(path: web::Path<String>, params: web::Query<ViewParams>) -> Result<HttpResponse, ActixError> {
+    render_view(&path.into_inner(), GroupBy::Agency, params.into_inner()).await
+}
+
+async fn by_date_month_year(path: web::Path<String>, params: web::Query<ViewParams>) -> Result<HttpResponse, ActixError> {
+    render_view(&path.into_inner(), GroupBy::DateMonthYear, params.into_inner()).await
+}
+
+async fn by_region(path: web::Path<String>, params: web::Query<ViewParams>) -> Result<HttpResponse, ActixError> {
+    render_view(&path.into_inner(), GroupBy::Region, params.into_inner()).await
+}
+
+// Register the `/api` aggregation subsystem.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    let scope = web::scope("/api")
+        .wrap(crate::cors::build())
+        .route("/{dataset}/byAgency", web::get().to(by_agency))
+        .route("/{dataset}/byDateMonthYear", web::get().to(by_date_month_year))
+        .route("/{dataset}/byRegion", web::get().to(by_region));
+    let scope = crate::correlation::config(scope);
+    cfg.service(crate::geojson::config(scope));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_to_month_handles_iso_date() {
+        assert_eq!(truncate_to_month("2024-03-15"), Some("2024-03".to_string()));
+    }
+
+    #[test]
+    fn truncate_to_month_handles_slash_separated_ymd() {
+        assert_eq!(truncate_to_month("2024/03/15"), Some("2024-03".to_string()));
+    }
+
+    #[test]
+    fn truncate_to_month_handles_us_style_mdy() {
+        assert_eq!(truncate_to_month("03/15/2024"), Some("2024-03".to_string()));
+    }
+
+    #[test]
+    fn truncate_to_month_rejects_unrecognized_shape() {
+        assert_eq!(truncate_to_month("not-a-date"), None);
+    }
+}
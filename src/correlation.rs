@@ -0,0 +1,182 @@
+// BetterGovPH Open Data Visualization - Server-side correlation analysis
+//
+// Backs the correlation pages with reproducible, testable numbers instead of
+// ad-hoc browser math. Two datasets are joined on a shared key (region or
+// agency), aligned into numeric series x and y, and summarized with Pearson's r
+// plus a least-squares regression line for the scatter overlay.
+
+use actix_web::{web, HttpResponse, Result, error::Error as ActixError};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::data::{self, Dataset};
+
+// One aligned observation over a shared join key.
+#[derive(Serialize)]
+struct Pair {
+    key: String,
+    x: f64,
+    y: f64,
+}
+
+// The least-squares regression line for the scatter overlay.
+#[derive(Serialize)]
+struct Regression {
+    slope: f64,
+    intercept: f64,
+}
+
+// The correlation response. `r` is null when either series has zero variance.
+#[derive(Serialize)]
+struct CorrelationResult {
+    r: Option<f64>,
+    n: usize,
+    pairs: Vec<Pair>,
+    regression: Option<Regression>,
+}
+
+// How a correlation pair is joined.
+#[derive(Clone, Copy)]
+enum JoinKey {
+    Agency,
+    Region,
+}
+
+// Sum the amount field per join key for a dataset.
+fn totals_by_key(dataset: Dataset, join: JoinKey) -> BTreeMap<String, f64> {
+    let fields = match join {
+        JoinKey::Agency => data::AGENCY_FIELDS,
+        JoinKey::Region => data::REGION_FIELDS,
+    };
+
+    let mut totals: BTreeMap<String, f64> = BTreeMap::new();
+    for record in data::load(dataset) {
+        if let Some(key) = data::field(&record, fields) {
+            let amount = data::field(&record, data::AMOUNT_FIELDS)
+                .and_then(data::parse_amount)
+                .unwrap_or(0.0);
+            *totals.entry(key.to_string()).or_insert(0.0) += amount;
+        }
+    }
+    totals
+}
+
+// Build the aligned pairs over the keys shared by both datasets.
+fn aligned_pairs(a: &BTreeMap<String, f64>, b: &BTreeMap<String, f64>) -> Vec<Pair> {
+    a.iter()
+        .filter_map(|(key, &x)| b.get(key).map(|&y| Pair { key: key.clone(), x, y }))
+        .collect()
+}
+
+// Compute Pearson's r and the regression line. Returns `r: None` when either
+// series has zero variance, and requires at least two observations.
+fn correlate(pairs: &[Pair]) -> CorrelationResult {
+    let n = pairs.len();
+    if n < 2 {
+        return CorrelationResult { r: None, n, pairs: Vec::new(), regression: None };
+    }
+
+    let count = n as f64;
+    let mean_x = pairs.iter().map(|p| p.x).sum::<f64>() / count;
+    let mean_y = pairs.iter().map(|p| p.y).sum::<f64>() / count;
+
+    let mut cov = 0.0;
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    for p in pairs {
+        let dx = p.x - mean_x;
+        let dy = p.y - mean_y;
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+
+    // Zero variance in either series makes r undefined; avoid dividing by zero.
+    if var_x == 0.0 || var_y == 0.0 {
+        return CorrelationResult {
+            r: None,
+            n,
+            pairs: pairs.iter().map(|p| Pair { key: p.key.clone(), x: p.x, y: p.y }).collect(),
+            regression: None,
+        };
+    }
+
+    let r = (cov / (var_x * var_y).sqrt()).clamp(-1.0, 1.0);
+    let slope = cov / var_x;
+    let intercept = mean_y - slope * mean_x;
+
+    CorrelationResult {
+        r: Some(r),
+        n,
+        pairs: pairs.iter().map(|p| Pair { key: p.key.clone(), x: p.x, y: p.y }).collect(),
+        regression: Some(Regression { slope, intercept }),
+    }
+}
+
+// Resolve a `{pair}` path segment into its two datasets and the join key.
+fn resolve_pair(pair: &str) -> Option<(Dataset, Dataset, JoinKey)> {
+    match pair {
+        "budget-nep" => Some((Dataset::Budget, Dataset::Nep, JoinKey::Agency)),
+        // Budget's declared schema (`Dataset::required_headers`) is
+        // agency-keyed with no region column, so joining on `Region` would
+        // never find a shared key and always return `r: null, n: 0`.
+        "budget-flood" => Some((Dataset::Budget, Dataset::Flood, JoinKey::Agency)),
+        "flood-dime" => Some((Dataset::Flood, Dataset::Dime, JoinKey::Region)),
+        _ => None,
+    }
+}
+
+async fn correlation(path: web::Path<String>) -> Result<HttpResponse, ActixError> {
+    let (a, b, join) = resolve_pair(&path.into_inner())
+        .ok_or_else(|| actix_web::error::ErrorNotFound("unknown correlation pair"))?;
+
+    let pairs = aligned_pairs(&totals_by_key(a, join), &totals_by_key(b, join));
+    Ok(HttpResponse::Ok().json(correlate(&pairs)))
+}
+
+// Register the correlation endpoint under the `/api` scope.
+pub fn config(scope: actix_web::Scope) -> actix_web::Scope {
+    scope.route("/correlation/{pair}", web::get().to(correlation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(key: &str, x: f64, y: f64) -> Pair {
+        Pair { key: key.to_string(), x, y }
+    }
+
+    #[test]
+    fn correlate_known_series_is_perfectly_correlated() {
+        // y = 2x + 1, so r should be 1.0 and the regression should recover it exactly.
+        let pairs = vec![pair("a", 1.0, 3.0), pair("b", 2.0, 5.0), pair("c", 3.0, 7.0), pair("d", 4.0, 9.0)];
+        let result = correlate(&pairs);
+
+        assert_eq!(result.n, 4);
+        assert!((result.r.unwrap() - 1.0).abs() < 1e-9);
+        let regression = result.regression.unwrap();
+        assert!((regression.slope - 2.0).abs() < 1e-9);
+        assert!((regression.intercept - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn correlate_requires_at_least_two_observations() {
+        let result = correlate(&[pair("a", 1.0, 2.0)]);
+        assert_eq!(result.n, 1);
+        assert_eq!(result.r, None);
+        assert!(result.regression.is_none());
+        assert!(result.pairs.is_empty());
+    }
+
+    #[test]
+    fn correlate_zero_variance_series_yields_null_r() {
+        // x is constant, so r is undefined rather than a division by zero.
+        let pairs = vec![pair("a", 5.0, 1.0), pair("b", 5.0, 2.0), pair("c", 5.0, 3.0)];
+        let result = correlate(&pairs);
+
+        assert_eq!(result.n, 3);
+        assert_eq!(result.r, None);
+        assert!(result.regression.is_none());
+    }
+}
@@ -0,0 +1,68 @@
+// BetterGovPH Open Data Visualization - CORS policy for the open-data API
+//
+// As an open-data project the `/api` datasets should be reusable by outside
+// researchers and apps. The policy is driven by environment variables read
+// alongside `load_frontend_env`, so deployments can open the data to everyone
+// (`*`), pin a single consumer, or restrict it to a known allow-list.
+
+use actix_cors::Cors;
+use actix_web::http::header;
+
+// Where cross-origin requests are allowed to come from.
+pub enum Origin {
+    // Allow any origin (`Access-Control-Allow-Origin: *`).
+    Star,
+    // Allow exactly one origin.
+    Single(String),
+    // Allow an explicit allow-list of origins.
+    List(Vec<String>),
+}
+
+impl Origin {
+    // Read the policy from `API_CORS_ALLOWED_ORIGINS`: unset or `*` means
+    // `Star`, a single value means `Single`, and a comma-separated value means
+    // `List`.
+    pub fn from_env() -> Origin {
+        match std::env::var("API_CORS_ALLOWED_ORIGINS") {
+            Ok(raw) => Origin::from_spec(&raw),
+            Err(_) => Origin::Star,
+        }
+    }
+
+    // Parse a raw spec string into an `Origin`.
+    pub fn from_spec(raw: &str) -> Origin {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed == "*" {
+            return Origin::Star;
+        }
+
+        let origins: Vec<String> = trimmed
+            .split(',')
+            .map(|o| o.trim().to_string())
+            .filter(|o| !o.is_empty())
+            .collect();
+
+        match origins.as_slice() {
+            [single] => Origin::Single(single.clone()),
+            _ => Origin::List(origins),
+        }
+    }
+}
+
+// Build an actix CORS middleware from the environment-driven policy, emitting
+// the correct `Access-Control-Allow-*` headers and handling `OPTIONS`
+// preflight requests for the open-data API.
+pub fn build() -> Cors {
+    let base = Cors::default()
+        .allowed_methods(vec!["GET", "POST", "OPTIONS"])
+        .allowed_headers(vec![header::ACCEPT, header::CONTENT_TYPE, header::AUTHORIZATION])
+        .max_age(3600);
+
+    match Origin::from_env() {
+        Origin::Star => base.allow_any_origin(),
+        Origin::Single(origin) => base.allowed_origin(&origin),
+        Origin::List(origins) => origins
+            .iter()
+            .fold(base, |cors, origin| cors.allowed_origin(origin)),
+    }
+}
@@ -0,0 +1,185 @@
+// BetterGovPH Open Data Visualization - Dataset loading and access
+//
+// The underlying open-data files live under `data/<dataset>.csv`. Rows are kept
+// as tolerant string maps (column name -> value) so the aggregation and GeoJSON
+// layers can look up fields by any of several candidate header names without a
+// rigid per-dataset schema. Loading is deliberately forgiving: unreadable or
+// absent files yield an empty record set rather than failing the whole request.
+
+use actix_web::{error::Error as ActixError, web, HttpResponse, Result};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+// The four datasets the platform visualizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dataset {
+    Budget,
+    Flood,
+    Dime,
+    Nep,
+}
+
+impl Dataset {
+    // Parse a path segment (`budget`, `flood`, `dime`, `nep`) into a dataset.
+    pub fn from_slug(slug: &str) -> Option<Dataset> {
+        match slug {
+            "budget" => Some(Dataset::Budget),
+            "flood" => Some(Dataset::Flood),
+            "dime" => Some(Dataset::Dime),
+            "nep" => Some(Dataset::Nep),
+            _ => None,
+        }
+    }
+
+    // Headers an uploaded file must contain to be accepted for this dataset.
+    // Validation is case-insensitive; extra columns are allowed.
+    pub fn required_headers(self) -> &'static [&'static str] {
+        match self {
+            Dataset::Budget => &["agency", "amount"],
+            Dataset::Flood => &["region", "amount"],
+            Dataset::Dime => &["region", "cost"],
+            Dataset::Nep => &["agency", "amount"],
+        }
+    }
+
+    // The CSV file backing this dataset.
+    pub fn csv_path(self) -> String {
+        let slug = match self {
+            Dataset::Budget => "budget",
+            Dataset::Flood => "flood",
+            Dataset::Dime => "dime",
+            Dataset::Nep => "nep",
+        };
+        format!("data/{slug}.csv")
+    }
+}
+
+// A single row as a column -> value map, keyed by the header exactly as it
+// appears in the file; `field` does the case-insensitive lookup.
+pub type Record = HashMap<String, String>;
+
+// Look up a field by trying each candidate header in order (case-insensitive),
+// returning the first non-empty match.
+pub fn field<'a>(record: &'a Record, candidates: &[&str]) -> Option<&'a str> {
+    for candidate in candidates {
+        for (key, value) in record {
+            if key.eq_ignore_ascii_case(candidate) && !value.trim().is_empty() {
+                return Some(value.trim());
+            }
+        }
+    }
+    None
+}
+
+// Candidate header names for the common grouping/aggregation fields. Government
+// CSV exports are inconsistent, so each concept lists several spellings.
+pub const AGENCY_FIELDS: &[&str] = &["implementing_agency", "agency", "department", "office"];
+pub const REGION_FIELDS: &[&str] = &["region", "province", "location", "municipality"];
+pub const DATE_FIELDS: &[&str] = &["date", "start_date", "completion_date", "date_awarded"];
+pub const AMOUNT_FIELDS: &[&str] = &["amount", "contract_cost", "cost", "budget", "abc"];
+
+// Process-wide cache of parsed records, keyed by CSV path. Populated lazily on
+// first read and cleared by `invalidate` after an ingestion replaces a file.
+fn cache() -> &'static RwLock<HashMap<String, Vec<Record>>> {
+    static CACHE: OnceLock<RwLock<HashMap<String, Vec<Record>>>> = OnceLock::new();
+    CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+// Drop the cached records for a dataset so the next read re-parses its file.
+pub fn invalidate(dataset: Dataset) {
+    if let Ok(mut cache) = cache().write() {
+        cache.remove(&dataset.csv_path());
+    }
+}
+
+// Load every record for a dataset. Results are cached per file; missing or
+// malformed files produce an empty set rather than an error, keeping the API
+// endpoints resilient.
+pub fn load(dataset: Dataset) -> Vec<Record> {
+    let path = dataset.csv_path();
+
+    if let Ok(cache) = cache().read() {
+        if let Some(records) = cache.get(&path) {
+            return records.clone();
+        }
+    }
+
+    let records = read_csv(&path);
+    if let Ok(mut cache) = cache().write() {
+        cache.insert(path, records.clone());
+    }
+    records
+}
+
+// Read and parse a CSV file into records, tolerating absence and malformed rows.
+fn read_csv(path: &str) -> Vec<Record> {
+    let mut reader = match csv::ReaderBuilder::new().flexible(true).from_path(path) {
+        Ok(reader) => reader,
+        Err(_) => return Vec::new(),
+    };
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(_) => return Vec::new(),
+    };
+
+    let mut records = Vec::new();
+    for row in reader.records().flatten() {
+        let mut record = Record::new();
+        for (header, value) in headers.iter().zip(row.iter()) {
+            record.insert(header.to_string(), value.to_string());
+        }
+        records.push(record);
+    }
+    records
+}
+
+// Parse a numeric amount tolerant of currency symbols, thousands separators and
+// surrounding whitespace.
+pub fn parse_amount(raw: &str) -> Option<f64> {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+        .collect();
+    if cleaned.is_empty() {
+        None
+    } else {
+        cleaned.parse::<f64>().ok()
+    }
+}
+
+// Serve the raw CSV backing a dataset (the primary artifact the DCAT/JSON-LD
+// distribution metadata points to), read straight off disk rather than from
+// the cached, parsed records.
+async fn raw_csv(path: web::Path<String>) -> Result<HttpResponse, ActixError> {
+    let dataset = Dataset::from_slug(&path.into_inner())
+        .ok_or_else(|| actix_web::error::ErrorNotFound("unknown dataset"))?;
+    let bytes = std::fs::read(dataset.csv_path())
+        .map_err(|_| actix_web::error::ErrorNotFound("dataset file not found"))?;
+    Ok(HttpResponse::Ok().content_type("text/csv").body(bytes))
+}
+
+// Register the raw CSV download route.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/data/{dataset}.csv", web::get().to(raw_csv));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_amount_strips_currency_symbols_and_separators() {
+        assert_eq!(parse_amount("₱1,234,567.89"), Some(1234567.89));
+    }
+
+    #[test]
+    fn parse_amount_handles_plain_whitespace_padded_number() {
+        assert_eq!(parse_amount("  42.5  "), Some(42.5));
+    }
+
+    #[test]
+    fn parse_amount_rejects_input_with_no_digits() {
+        assert_eq!(parse_amount("n/a"), None);
+    }
+}
@@ -0,0 +1,59 @@
+// BetterGovPH Open Data Visualization - Embeddable widgets
+//
+// `/embed/{page}` renders a chrome-free version of each visualization (no site
+// nav, no Open Graph block) using a dedicated `embed/*.html` layout that is
+// responsive to the iframe width. Main pages receive a ready-made
+// `<iframe>` snippet in their Tera context for an "Embed this chart" UI, and
+// embed responses carry permissive framing/CORS headers so they work
+// cross-origin on external sites.
+
+use actix_web::{web, HttpResponse, Result, error::Error as ActixError};
+use tera::Context;
+
+use crate::templates::SharedTera;
+
+// Pages that have an embeddable widget.
+const EMBEDDABLE: &[&str] = &["budget", "flood", "dime", "nep", "map"];
+
+// The copy-paste `<iframe>` snippet for a page, or `None` if it is not
+// embeddable.
+pub fn snippet(page: &str) -> Option<String> {
+    if EMBEDDABLE.contains(&page) {
+        Some(format!(
+            "<iframe src=\"https://altgovph.site/embed/{page}\" width=\"100%\" height=\"600\" frameborder=\"0\" style=\"border:0\" loading=\"lazy\"></iframe>"
+        ))
+    } else {
+        None
+    }
+}
+
+async fn embed(
+    path: web::Path<String>,
+    tera: web::Data<SharedTera>,
+) -> Result<HttpResponse, ActixError> {
+    let page = path.into_inner();
+    if !EMBEDDABLE.contains(&page.as_str()) {
+        return Err(actix_web::error::ErrorNotFound("no embeddable widget for page"));
+    }
+
+    let mut context = Context::new();
+    context.insert("page", &page);
+    context.insert("SITE_URL", "https://altgovph.site");
+
+    let template_name = format!("embed/{page}.html");
+    let rendered = tera
+        .render(&template_name, &context)
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    // Permissive framing/CORS so external sites can embed the widget.
+    Ok(HttpResponse::Ok()
+        .content_type("text/html")
+        .append_header(("Access-Control-Allow-Origin", "*"))
+        .append_header(("Content-Security-Policy", "frame-ancestors *"))
+        .body(rendered))
+}
+
+// Register the `/embed/{page}` route family.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/embed/{page}", web::get().to(embed));
+}
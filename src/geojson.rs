@@ -0,0 +1,125 @@
+// BetterGovPH Open Data Visualization - GeoJSON project locations
+//
+// Emits a standards-compliant GeoJSON `FeatureCollection` for the flood-control
+// and DIME project datasets so the Leaflet/MapLibre frontend can load markers
+// directly. Records whose coordinates are missing or unparseable are skipped
+// rather than failing the whole response. Registered under the `/api` scope
+// so it shares that scope's CORS policy with the other open-data endpoints.
+
+use actix_web::{web, HttpResponse, Result, error::Error as ActixError};
+use serde_json::{json, Value};
+
+use crate::data::{self, Dataset};
+
+// Candidate header names for coordinates.
+const LAT_FIELDS: &[&str] = &["latitude", "lat", "y"];
+const LON_FIELDS: &[&str] = &["longitude", "lon", "lng", "x"];
+const GEO_FIELDS: &[&str] = &["geo", "coordinates", "location_geo"];
+
+// A parsed `(latitude, longitude)` pair.
+struct LatLon {
+    lat: f64,
+    lon: f64,
+}
+
+// Tolerant coordinate parser. Accepts either separate lat/lon columns or a
+// combined `geo:<lat>,<lon>[;...]` string: split on `:`, take the part before
+// `;`, then split the remaining `lat,lon` on `,`.
+fn parse_coordinates(record: &data::Record) -> Option<LatLon> {
+    if let (Some(lat), Some(lon)) = (
+        data::field(record, LAT_FIELDS),
+        data::field(record, LON_FIELDS),
+    ) {
+        if let (Ok(lat), Ok(lon)) = (lat.parse::<f64>(), lon.parse::<f64>()) {
+            return Some(LatLon { lat, lon });
+        }
+    }
+
+    let raw = data::field(record, GEO_FIELDS)?;
+    let after_scheme = raw.rsplit(':').next()?;
+    let first = after_scheme.split(';').next()?;
+    let mut parts = first.split(',');
+    let lat = parts.next()?.trim().parse::<f64>().ok()?;
+    let lon = parts.next()?.trim().parse::<f64>().ok()?;
+    Some(LatLon { lat, lon })
+}
+
+// Build a single GeoJSON `Feature` for a record with valid coordinates.
+fn feature(record: &data::Record) -> Option<Value> {
+    let LatLon { lat, lon } = parse_coordinates(record)?;
+    Some(json!({
+        "type": "Feature",
+        "geometry": {
+            "type": "Point",
+            "coordinates": [lon, lat]
+        },
+        "properties": {
+            "name": data::field(record, &["project_name", "name", "title", "description"]),
+            "cost": data::field(record, data::AMOUNT_FIELDS),
+            "agency": data::field(record, data::AGENCY_FIELDS),
+            "status": data::field(record, &["status", "project_status"])
+        }
+    }))
+}
+
+// Render `/api/map/{dataset}.geojson` for the flood-control and DIME datasets.
+async fn map_geojson(path: web::Path<String>) -> Result<HttpResponse, ActixError> {
+    let slug = path.into_inner();
+    let dataset = Dataset::from_slug(&slug)
+        .filter(|d| matches!(d, Dataset::Flood | Dataset::Dime))
+        .ok_or_else(|| actix_web::error::ErrorNotFound("no geographic data for dataset"))?;
+
+    let features: Vec<Value> = data::load(dataset).iter().filter_map(feature).collect();
+
+    let collection = json!({
+        "type": "FeatureCollection",
+        "features": features
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/geo+json")
+        .json(collection))
+}
+
+// Register the GeoJSON map endpoint under the `/api` scope.
+pub fn config(scope: web::Scope) -> web::Scope {
+    scope.route("/map/{dataset}.geojson", web::get().to(map_geojson))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn record(pairs: &[(&str, &str)]) -> data::Record {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>()
+    }
+
+    #[test]
+    fn parses_separate_lat_lon_columns() {
+        let r = record(&[("latitude", "14.5"), ("longitude", "121.0")]);
+        let coords = parse_coordinates(&r).unwrap();
+        assert_eq!(coords.lat, 14.5);
+        assert_eq!(coords.lon, 121.0);
+    }
+
+    #[test]
+    fn parses_combined_geo_string_with_scheme_and_trailer() {
+        let r = record(&[("geo", "geo:14.5,121.0;crs=wgs84")]);
+        let coords = parse_coordinates(&r).unwrap();
+        assert_eq!(coords.lat, 14.5);
+        assert_eq!(coords.lon, 121.0);
+    }
+
+    #[test]
+    fn skips_record_with_unparseable_coordinates() {
+        let r = record(&[("geo", "not-a-coordinate")]);
+        assert!(parse_coordinates(&r).is_none());
+    }
+
+    #[test]
+    fn skips_record_with_no_coordinate_fields_at_all() {
+        let r = record(&[("project_name", "Example")]);
+        assert!(parse_coordinates(&r).is_none());
+    }
+}
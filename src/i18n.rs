@@ -0,0 +1,108 @@
+// BetterGovPH Open Data Visualization - Localization (English / Filipino)
+//
+// The preferred language is taken from an explicit `?lang=` override, then the
+// sticky `lang` cookie, then the `Accept-Language` header. Per-locale string tables are injected into
+// the Tera context, and the resolved language code is exposed as `lang` so the
+// base template can set the correct `<html lang="...">` for screen readers.
+
+use actix_web::HttpRequest;
+use std::collections::HashMap;
+
+// Supported locales. English is the default fallback.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Filipino,
+}
+
+impl Locale {
+    // BCP 47 language code used for the `lang` attribute.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Filipino => "fil",
+        }
+    }
+
+    // Parse a language tag prefix (`en`, `fil`, `tl`) into a locale.
+    fn from_tag(tag: &str) -> Option<Locale> {
+        match tag.trim().to_ascii_lowercase().split(['-', ';']).next()? {
+            "en" => Some(Locale::English),
+            "fil" | "tl" => Some(Locale::Filipino),
+            _ => None,
+        }
+    }
+}
+
+// Resolve the locale from a `?lang=` override, then the sticky `lang` cookie,
+// then `Accept-Language`, falling back to English.
+pub fn detect(req: &HttpRequest) -> Locale {
+    if let Some(query) = req.uri().query() {
+        for pair in query.split('&') {
+            if let Some(value) = pair.strip_prefix("lang=") {
+                if let Some(locale) = Locale::from_tag(value) {
+                    return locale;
+                }
+            }
+        }
+    }
+
+    if let Some(cookie) = req.cookie("lang") {
+        if let Some(locale) = Locale::from_tag(cookie.value()) {
+            return locale;
+        }
+    }
+
+    if let Some(header) = req.headers().get("accept-language") {
+        if let Ok(header) = header.to_str() {
+            for tag in header.split(',') {
+                if let Some(locale) = Locale::from_tag(tag) {
+                    return locale;
+                }
+            }
+        }
+    }
+
+    Locale::English
+}
+
+// Pick the localized template for the request's locale when one exists under
+// `templates/{locale}/...`, otherwise fall back to the default template.
+pub fn resolve(req: &HttpRequest, base_name: &str) -> String {
+    let locale = detect(req);
+    if locale == Locale::English {
+        return base_name.to_string();
+    }
+    let localized = format!("{}/{}", locale.code(), base_name);
+    if crate::templates::template_exists(&localized) {
+        localized
+    } else {
+        base_name.to_string()
+    }
+}
+
+// The per-locale string table injected into the Tera context under `i18n`.
+pub fn strings(locale: Locale) -> HashMap<&'static str, &'static str> {
+    let mut table = HashMap::new();
+    match locale {
+        Locale::English => {
+            table.insert("budget", "Budget Analysis");
+            table.insert("flood", "Flood Control Projects");
+            table.insert("dime", "Infrastructure Projects");
+            table.insert("nep", "National Expenditure Program");
+            table.insert("about", "About");
+            table.insert("map", "Interactive Map");
+            table.insert("tagline", "Promoting data transparency and open government in the Philippines");
+        }
+        Locale::Filipino => {
+            table.insert("budget", "Pagsusuri ng Badyet");
+            table.insert("flood", "Mga Proyekto sa Kontrol sa Baha");
+            table.insert("dime", "Mga Proyektong Imprastraktura");
+            table.insert("nep", "Pambansang Programa sa Gastos");
+            table.insert("about", "Tungkol Dito");
+            table.insert("map", "Interaktibong Mapa");
+            table.insert("tagline", "Itinataguyod ang transparency ng datos at bukas na pamahalaan sa Pilipinas");
+        }
+    }
+    table
+}
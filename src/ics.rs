@@ -0,0 +1,194 @@
+// BetterGovPH Open Data Visualization - iCalendar (.ics) project timelines
+//
+// Emits an RFC 5545 VCALENDAR where each infrastructure project becomes a
+// VEVENT, so citizens and journalists can subscribe to project schedules in
+// their calendar apps. The format's hard rules are honoured: CRLF line endings,
+// the mandatory VCALENDAR envelope, escaping of commas/semicolons/newlines in
+// text, and folding of lines longer than 75 octets.
+
+use actix_web::{web, HttpResponse, Result, error::Error as ActixError};
+
+use crate::data::{self, Dataset};
+
+// Escape a text value per RFC 5545 section 3.3.11.
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Fold a single content line to 75 octets, continuing with CRLF + a single
+// space as required by section 3.1.
+fn fold_line(line: &str, out: &mut String) {
+    let bytes = line.as_bytes();
+    if bytes.len() <= 75 {
+        out.push_str(line);
+        out.push_str("\r\n");
+        return;
+    }
+
+    let mut start = 0;
+    let mut first = true;
+    while start < bytes.len() {
+        // Leave room for the leading space on continuation lines.
+        let limit = if first { 75 } else { 74 };
+        let mut end = (start + limit).min(bytes.len());
+        // Do not split inside a UTF-8 multibyte sequence: back off to the
+        // nearest preceding char boundary rather than just skipping
+        // continuation bytes, which could otherwise strand a lead byte alone.
+        while end > start && !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        if !first {
+            out.push(' ');
+        }
+        out.push_str(&line[start..end]);
+        out.push_str("\r\n");
+        start = end;
+        first = false;
+    }
+}
+
+// Append a folded `NAME:value` property line.
+fn property(out: &mut String, name: &str, value: &str) {
+    fold_line(&format!("{name}:{value}"), out);
+}
+
+// Normalize a date string to an RFC 5545 `VALUE=DATE` form (`YYYYMMDD`),
+// tolerating `YYYY-MM-DD` and `YYYY/MM/DD`.
+fn as_date(raw: &str) -> Option<String> {
+    let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() == 8 {
+        Some(digits)
+    } else {
+        None
+    }
+}
+
+// Build the VCALENDAR text for a dataset's project records.
+fn build_calendar(dataset: Dataset) -> String {
+    let mut out = String::new();
+    out.push_str("BEGIN:VCALENDAR\r\n");
+    out.push_str("VERSION:2.0\r\n");
+    out.push_str("PRODID:-//BetterGovPH//Open Data Visualization//EN\r\n");
+
+    for (index, record) in data::load(dataset).iter().enumerate() {
+        out.push_str("BEGIN:VEVENT\r\n");
+
+        let uid = data::field(record, &["id", "project_id", "contract_id"])
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}-{index}", dataset.csv_path()));
+        property(&mut out, "UID", &format!("{uid}@altgovph.site"));
+
+        if let Some(name) = data::field(record, &["project_name", "name", "title", "description"]) {
+            property(&mut out, "SUMMARY", &escape_text(name));
+        }
+
+        if let Some(start) = data::field(record, &["start_date", "date_awarded", "date"])
+            .and_then(as_date)
+        {
+            property(&mut out, "DTSTART;VALUE=DATE", &start);
+        }
+        if let Some(end) = data::field(record, &["completion_date", "end_date"]).and_then(as_date) {
+            property(&mut out, "DTEND;VALUE=DATE", &end);
+        }
+
+        if let Some(location) = data::field(record, data::REGION_FIELDS) {
+            property(&mut out, "LOCATION", &escape_text(location));
+        }
+
+        let contractor = data::field(record, &["contractor", "implementing_agency", "agency"]).unwrap_or("");
+        let budget = data::field(record, data::AMOUNT_FIELDS).unwrap_or("");
+        let description = format!("Contractor: {contractor}; Budget: {budget}");
+        property(&mut out, "DESCRIPTION", &escape_text(&description));
+
+        out.push_str("END:VEVENT\r\n");
+    }
+
+    out.push_str("END:VCALENDAR\r\n");
+    out
+}
+
+async fn calendar(dataset: Dataset, filename: &str) -> Result<HttpResponse, ActixError> {
+    let body = build_calendar(dataset);
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .append_header(("Content-Disposition", format!("attachment; filename=\"{filename}\"")))
+        .body(body))
+}
+
+async fn dime_ics() -> Result<HttpResponse, ActixError> {
+    calendar(Dataset::Dime, "dime.ics").await
+}
+
+async fn flood_ics() -> Result<HttpResponse, ActixError> {
+    calendar(Dataset::Flood, "flood.ics").await
+}
+
+// Register the `.ics` export routes.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/dime.ics", web::get().to(dime_ics))
+        .route("/flood.ics", web::get().to(flood_ics));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_text_escapes_special_characters() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+    }
+
+    #[test]
+    fn fold_line_leaves_short_lines_untouched() {
+        let mut out = String::new();
+        fold_line("SUMMARY:short", &mut out);
+        assert_eq!(out, "SUMMARY:short\r\n");
+    }
+
+    #[test]
+    fn fold_line_wraps_at_75_octets_with_continuation_space() {
+        let value = "x".repeat(100);
+        let line = format!("SUMMARY:{value}");
+        let mut out = String::new();
+        fold_line(&line, &mut out);
+
+        let segments: Vec<&str> = out.split("\r\n").filter(|s| !s.is_empty()).collect();
+        assert!(segments.len() > 1);
+        assert!(segments[0].as_bytes().len() <= 75);
+        for continuation in &segments[1..] {
+            assert!(continuation.starts_with(' '));
+            assert!(continuation.as_bytes().len() <= 75);
+        }
+        // Rejoining the unfolded content (minus the continuation space) recovers the original line.
+        let rejoined: String = segments.iter().enumerate()
+            .map(|(i, s)| if i == 0 { *s } else { &s[1..] })
+            .collect();
+        assert_eq!(rejoined, line);
+    }
+
+    #[test]
+    fn fold_line_does_not_split_a_multibyte_char_on_the_boundary() {
+        // Sweep the padding so the 75-octet cut point lands on every byte of
+        // the 3-octet euro sign at least once across the sweep.
+        for pad in 60..70 {
+            let line = format!(
+                "SUMMARY:{}\u{20ac}more text after the split point to force folding",
+                "x".repeat(pad)
+            );
+            let mut out = String::new();
+            fold_line(&line, &mut out);
+
+            let rejoined: String = out
+                .split("\r\n")
+                .filter(|s| !s.is_empty())
+                .enumerate()
+                .map(|(i, s)| if i == 0 { s } else { &s[1..] })
+                .collect();
+            assert_eq!(rejoined, line, "round-trip failed for pad={pad}");
+        }
+    }
+}
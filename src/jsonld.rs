@@ -0,0 +1,106 @@
+// BetterGovPH Open Data Visualization - Machine-readable dataset descriptions
+//
+// Mirrors the RDF/JSON-LD distribution markup that open-data CKAN portals
+// expose. Each dataset page can be content-negotiated to a schema.org `Dataset`
+// document enriched with Dublin Core / AGLS fields, and the same metadata is
+// injected into the HTML as a `<script type="application/ld+json">` block for
+// crawlers.
+
+use actix_web::{HttpRequest, HttpResponse};
+use serde_json::{json, Value};
+
+use crate::data::Dataset;
+
+// Human-facing metadata for a dataset's JSON-LD description.
+struct Meta {
+    slug: &'static str,
+    title: &'static str,
+    description: &'static str,
+}
+
+fn meta(dataset: Dataset) -> Meta {
+    match dataset {
+        Dataset::Budget => Meta {
+            slug: "budget",
+            title: "Philippine National Budget",
+            description: "General Appropriations Act allocations by implementing agency.",
+        },
+        Dataset::Flood => Meta {
+            slug: "flood",
+            title: "Flood Control Projects",
+            description: "Flood-control infrastructure projects and their funding.",
+        },
+        Dataset::Dime => Meta {
+            slug: "dime",
+            title: "DIME Infrastructure Projects",
+            description: "Department of Infrastructure and Mega-Projects Execution project tracker.",
+        },
+        Dataset::Nep => Meta {
+            slug: "nep",
+            title: "National Expenditure Program",
+            description: "Proposed national expenditure program allocations.",
+        },
+    }
+}
+
+// Build the JSON-LD `Dataset` document with Dublin Core / AGLS and DCAT
+// distribution fields.
+pub fn document(dataset: Dataset) -> Value {
+    let meta = meta(dataset);
+    let site = "https://altgovph.site";
+    json!({
+        "@context": {
+            "@vocab": "https://schema.org/",
+            "dc": "http://purl.org/dc/elements/1.1/",
+            "dct": "http://purl.org/dc/terms/",
+            "dcat": "http://www.w3.org/ns/dcat#"
+        },
+        "@type": "Dataset",
+        "dc:title": meta.title,
+        "name": meta.title,
+        "description": meta.description,
+        "dc:publisher": "BetterGovPH",
+        "publisher": { "@type": "Organization", "name": "BetterGovPH" },
+        "dct:spatial": "Philippines",
+        "dcat:keyword": ["open-data", "philippines", "government", "transparency", meta.slug],
+        "dcat:distribution": [
+            {
+                "@type": "dcat:Distribution",
+                "encodingFormat": "text/csv",
+                "contentUrl": format!("{site}/data/{}.csv", meta.slug)
+            },
+            {
+                "@type": "dcat:Distribution",
+                "encodingFormat": "application/json",
+                "contentUrl": format!("{site}/api/{}/byAgency", meta.slug)
+            }
+        ]
+    })
+}
+
+// True when the client asked for JSON-LD via the `Accept` header or a
+// `?format=jsonld|rdf` query parameter.
+pub fn wants_jsonld(req: &HttpRequest) -> bool {
+    if let Some(accept) = req.headers().get("accept").and_then(|h| h.to_str().ok()) {
+        if accept.contains("application/ld+json") {
+            return true;
+        }
+    }
+    if let Some(query) = req.uri().query() {
+        for pair in query.split('&') {
+            if let Some(format) = pair.strip_prefix("format=") {
+                if format == "jsonld" || format == "rdf" {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+// The negotiated JSON-LD HTTP response for a dataset.
+pub fn response(dataset: Dataset) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/ld+json")
+        .json(document(dataset))
+}
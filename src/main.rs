@@ -1,122 +1,247 @@
 // BetterGovPH Open Data Visualization - Standalone Application
 
 use actix_web::{App, HttpServer, HttpResponse, HttpRequest, Result, error::Error as ActixError, web};
-use actix_files as fs;
-use tera::{Tera, Context};
+use tera::Context;
 use std::collections::HashMap;
 
+mod api;
+mod correlation;
+mod cors;
+mod data;
+mod embed;
+mod geojson;
+mod i18n;
+mod ics;
+mod jsonld;
+mod metrics;
+mod mobile;
+mod pwa;
+mod templates;
+mod upload;
+
 // Helper functions
 
 // Function: add_frontend_env_to_context
-fn add_frontend_env_to_context(context: &mut tera::Context) {
+fn add_frontend_env_to_context(req: &HttpRequest, context: &mut tera::Context) {
     let mut env_vars = HashMap::new();
     env_vars.insert("SITE_URL".to_string(), "https://altgovph.site".to_string());
     env_vars.insert("SITE_NAME".to_string(), "BetterGovPH Data Visualizations".to_string());
-    
+
     for (key, value) in env_vars {
         context.insert(&key, &value);
     }
-}
 
-// Function: should_use_mobile_template
-fn should_use_mobile_template(_req: &HttpRequest) -> bool {
-    false  // Disabled for this standalone app
-}
+    // Localization: resolve the locale once here so every page is translated and
+    // carries the correct `lang` attribute without per-handler logic.
+    let locale = i18n::detect(req);
+    context.insert("lang", locale.code());
+    context.insert("content_lang", locale.code());
+    context.insert("i18n", &i18n::strings(locale));
+
+    // Provide a ready-made embed snippet for pages that have a widget, so an
+    // "Embed this chart" UI can display it.
+    let page = req.uri().path().trim_start_matches('/');
+    if let Some(snippet) = embed::snippet(page) {
+        context.insert("embed_code", &snippet);
+    }
 
-// Function: check_mobile_redirect_enhanced
-fn check_mobile_redirect_enhanced(_req: &HttpRequest) -> Option<actix_web::HttpResponse> {
-    None  // Disabled for this standalone app
+    // Cache-busting version for the PWA service worker registration.
+    context.insert("sw_version", pwa::sw_version());
 }
 
+// Mobile detection and redirect live in the `mobile` module.
+use mobile::{check_mobile_redirect_enhanced, should_use_mobile_template};
+
 // Function: check_production_domain_block
 fn check_production_domain_block(_req: &HttpRequest) -> Option<actix_web::HttpResponse> {
     None  // Disabled for this standalone app
 }
 
+// Run the routing decisions for a page request, recording each outcome in the
+// metrics subsystem. Returns an early response (redirect/block) when one
+// applies, otherwise `None` after recording a `served` decision.
+fn gate(req: &HttpRequest, route: &str) -> Option<HttpResponse> {
+    metrics::record_request(route);
+    if let Some(redirect) = check_mobile_redirect_enhanced(req) {
+        metrics::record_decision(route, "redirected");
+        return Some(redirect);
+    }
+    if let Some(blocked) = check_production_domain_block(req) {
+        metrics::record_decision(route, "blocked");
+        return Some(blocked);
+    }
+    metrics::record_decision(route, "served");
+    None
+}
+
+// Serve a static asset from the embedded store (replaces `fs::Files`).
+async fn static_asset(path: web::Path<String>) -> HttpResponse {
+    let path = path.into_inner();
+    match templates::StaticAssets::get(&path) {
+        Some(content) => {
+            let mime = mime_guess::from_path(&path).first_or_octet_stream();
+            HttpResponse::Ok()
+                .content_type(mime.as_ref())
+                .body(content.data.into_owned())
+        }
+        None => HttpResponse::NotFound().body("404 Not Found"),
+    }
+}
+
 // Route handlers
 
 // BetterGovPH Homepage
-async fn altgovph_home(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn altgovph_home(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
     let mut context = Context::new();
-    
-    add_frontend_env_to_context(&mut context);
-    
+
+    add_frontend_env_to_context(&req, &mut context);
+
     context.insert("title", "BetterGovPH Data Visualizations");
     context.insert("company_name", "BetterGovPH");
     context.insert("platform", "BetterGovPH");
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
-    
-    let template_name = "visualizations_home.html";
-    
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/visualizations_home.html"
+    } else {
+        "visualizations_home.html"
+    };
+
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // Budget Analysis Page
-async fn budget(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn budget(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
+    // Content negotiation: serve the machine-readable dataset description.
+    if jsonld::wants_jsonld(&req) {
+        return Ok(jsonld::response(data::Dataset::Budget));
+    }
+
     let mut context = Context::new();
-    
-    add_frontend_env_to_context(&mut context);
-    
+
+    add_frontend_env_to_context(&req, &mut context);
+
     context.insert("title", "Budget Analysis - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
     context.insert("platform", "BetterGovPH");
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
-    
-    let template_name = "budget.html";
-    
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+
+    // Embed the same metadata for crawlers.
+    context.insert("jsonld", &jsonld::document(data::Dataset::Budget).to_string());
+
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/budget.html"
+    } else {
+        "budget.html"
+    };
+
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // Flood Control Projects Page
-async fn flood(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn flood(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
+    // Content negotiation: serve the machine-readable dataset description.
+    if jsonld::wants_jsonld(&req) {
+        return Ok(jsonld::response(data::Dataset::Flood));
+    }
+
     let mut context = Context::new();
-    
-    add_frontend_env_to_context(&mut context);
-    
+
+    add_frontend_env_to_context(&req, &mut context);
+
     context.insert("title", "Flood Control Projects - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
     context.insert("platform", "BetterGovPH");
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
-    
-    let template_name = "flood.html";
-    
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+
+    // Embed the same metadata for crawlers.
+    context.insert("jsonld", &jsonld::document(data::Dataset::Flood).to_string());
+
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/flood.html"
+    } else {
+        "flood.html"
+    };
+
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // DIME Infrastructure Projects Page
-async fn dime(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn dime(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
+    // Content negotiation: serve the machine-readable dataset description.
+    if jsonld::wants_jsonld(&req) {
+        return Ok(jsonld::response(data::Dataset::Dime));
+    }
+
     let mut context = Context::new();
-    
-    add_frontend_env_to_context(&mut context);
-    
+
+    add_frontend_env_to_context(&req, &mut context);
+
     context.insert("title", "DIME Infrastructure Projects - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
     context.insert("platform", "BetterGovPH");
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
-    
-    let template_name = "dime.html";
-    
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+
+    // Embed the same metadata for crawlers.
+    context.insert("jsonld", &jsonld::document(data::Dataset::Dime).to_string());
+
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/dime.html"
+    } else {
+        "dime.html"
+    };
+
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // NEP Analysis Page
-async fn nep(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn nep(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
+    // Content negotiation: serve the machine-readable dataset description.
+    if jsonld::wants_jsonld(&req) {
+        return Ok(jsonld::response(data::Dataset::Nep));
+    }
+
     let mut context = Context::new();
 
-    add_frontend_env_to_context(&mut context);
+    add_frontend_env_to_context(&req, &mut context);
 
     context.insert("title", "NEP Analysis - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
@@ -124,18 +249,30 @@ async fn nep(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
 
-    let template_name = "nep.html";
+    // Embed the same metadata for crawlers.
+    context.insert("jsonld", &jsonld::document(data::Dataset::Nep).to_string());
+
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/nep.html"
+    } else {
+        "nep.html"
+    };
 
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // Interactive Map Page
-async fn map(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn map(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
     let mut context = Context::new();
 
-    add_frontend_env_to_context(&mut context);
+    add_frontend_env_to_context(&req, &mut context);
 
     context.insert("title", "Interactive Map - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
@@ -143,18 +280,27 @@ async fn map(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
 
-    let template_name = "map.html";
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/map.html"
+    } else {
+        "map.html"
+    };
 
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // About Page
-async fn about(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn about(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
     let mut context = Context::new();
 
-    add_frontend_env_to_context(&mut context);
+    add_frontend_env_to_context(&req, &mut context);
 
     context.insert("title", "About - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
@@ -162,18 +308,27 @@ async fn about(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
 
-    let template_name = "about.html";
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/about.html"
+    } else {
+        "about.html"
+    };
 
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // Budget-NEP Correlation Page
-async fn budget_nep_correlation(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn budget_nep_correlation(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
     let mut context = Context::new();
 
-    add_frontend_env_to_context(&mut context);
+    add_frontend_env_to_context(&req, &mut context);
 
     context.insert("title", "Budget-NEP Correlation - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
@@ -181,18 +336,27 @@ async fn budget_nep_correlation(_req: HttpRequest) -> Result<HttpResponse, Actix
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
 
-    let template_name = "budget_nep_correlation.html";
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/budget_nep_correlation.html"
+    } else {
+        "budget_nep_correlation.html"
+    };
 
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // Budget-Flood Correlation Page
-async fn budget_flood_correlation(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn budget_flood_correlation(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
     let mut context = Context::new();
 
-    add_frontend_env_to_context(&mut context);
+    add_frontend_env_to_context(&req, &mut context);
 
     context.insert("title", "Budget-Flood Correlation - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
@@ -200,18 +364,27 @@ async fn budget_flood_correlation(_req: HttpRequest) -> Result<HttpResponse, Act
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
 
-    let template_name = "budget_flood_correlation.html";
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/budget_flood_correlation.html"
+    } else {
+        "budget_flood_correlation.html"
+    };
 
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 // Flood-DIME Correlation Page
-async fn flood_dime_correlation(_req: HttpRequest) -> Result<HttpResponse, ActixError> {
-    let tera = Tera::new("templates/**/*").map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+async fn flood_dime_correlation(req: HttpRequest, tera: web::Data<templates::SharedTera>) -> Result<HttpResponse, ActixError> {
+    let route = req.uri().path().to_string();
+    if let Some(response) = gate(&req, &route) {
+        return Ok(response);
+    }
+    let _timer = metrics::RenderTimer::new(&route);
+
     let mut context = Context::new();
 
-    add_frontend_env_to_context(&mut context);
+    add_frontend_env_to_context(&req, &mut context);
 
     context.insert("title", "Flood-DIME Correlation - BetterGovPH");
     context.insert("company_name", "BetterGovPH");
@@ -219,19 +392,36 @@ async fn flood_dime_correlation(_req: HttpRequest) -> Result<HttpResponse, Actix
     context.insert("SITE_NAME", "BetterGovPH Data Visualizations");
     context.insert("SITE_URL", "https://altgovph.site");
 
-    let template_name = "flood_dime_correlation.html";
+    let template_name = if should_use_mobile_template(&req) {
+        "mobile/flood_dime_correlation.html"
+    } else {
+        "flood_dime_correlation.html"
+    };
 
-    let rendered = tera.render(template_name, &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(rendered))
+    let rendered = tera.render(&i18n::resolve(&req, template_name), &context).map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
+    Ok(mobile::finalize(&req, rendered))
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     println!("🚀 Starting BetterGovPH Open Data Visualization Server");
-    
-    HttpServer::new(|| {
+
+    // Compile Tera once at startup from the embedded templates and share the
+    // single instance across all workers via `web::Data`.
+    let tera = templates::SharedTera::new().expect("failed to compile embedded templates");
+    let tera_data = web::Data::new(tera);
+
+    HttpServer::new(move || {
         App::new()
-            .service(fs::Files::new("/static", "./static/"))
+            .app_data(tera_data.clone())
+            .route("/static/{path:.*}", web::get().to(static_asset))
+            .configure(api::config)
+            .configure(data::config)
+            .configure(embed::config)
+            .configure(ics::config)
+            .configure(metrics::config)
+            .configure(pwa::config)
+            .configure(upload::config)
             .service(web::resource("/").to(altgovph_home))
             .service(web::resource("/budget").to(budget))
             .service(web::resource("/flood").to(flood))
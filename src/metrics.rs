@@ -0,0 +1,102 @@
+// BetterGovPH Open Data Visualization - Request/redirect metrics
+//
+// A lightweight metrics subsystem so operators can chart how the production
+// domain block and mobile redirects behave in practice. Decisions made while
+// serving a page are bucketed into labeled counters (route, decision =
+// `served|redirected|blocked`), per-route request counts are tracked, and
+// render latency is recorded in a histogram. Everything is exposed at `/metrics`
+// in the Prometheus text exposition format.
+
+use actix_web::{web, HttpResponse};
+use prometheus::{
+    Encoder, HistogramVec, IntCounterVec, Registry, TextEncoder,
+    histogram_opts, opts,
+};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+// The process-wide metric registry and the metric families registered in it.
+struct Metrics {
+    registry: Registry,
+    requests_total: IntCounterVec,
+    decisions_total: IntCounterVec,
+    render_latency: HistogramVec,
+}
+
+fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| {
+        let registry = Registry::new();
+
+        let requests_total = IntCounterVec::new(
+            opts!("altgov_requests_total", "Total requests served per route."),
+            &["route"],
+        )
+        .expect("valid metric");
+        let decisions_total = IntCounterVec::new(
+            opts!("altgov_route_decisions_total", "Routing decisions by outcome."),
+            &["route", "decision"],
+        )
+        .expect("valid metric");
+        let render_latency = HistogramVec::new(
+            histogram_opts!("altgov_render_latency_seconds", "Template render latency per route."),
+            &["route"],
+        )
+        .expect("valid metric");
+
+        registry.register(Box::new(requests_total.clone())).ok();
+        registry.register(Box::new(decisions_total.clone())).ok();
+        registry.register(Box::new(render_latency.clone())).ok();
+
+        Metrics { registry, requests_total, decisions_total, render_latency }
+    })
+}
+
+// Increment the request counter for a route.
+pub fn record_request(route: &str) {
+    metrics().requests_total.with_label_values(&[route]).inc();
+}
+
+// Record the routing decision taken for a route (`served`/`redirected`/`blocked`).
+pub fn record_decision(route: &str, decision: &str) {
+    metrics().decisions_total.with_label_values(&[route, decision]).inc();
+}
+
+// A RAII timer that observes render latency for a route when dropped.
+pub struct RenderTimer {
+    route: String,
+    start: Instant,
+}
+
+impl RenderTimer {
+    pub fn new(route: &str) -> RenderTimer {
+        RenderTimer { route: route.to_string(), start: Instant::now() }
+    }
+}
+
+impl Drop for RenderTimer {
+    fn drop(&mut self) {
+        metrics()
+            .render_latency
+            .with_label_values(&[&self.route])
+            .observe(self.start.elapsed().as_secs_f64());
+    }
+}
+
+// Expose all metrics in the Prometheus text exposition format.
+async fn scrape() -> HttpResponse {
+    let metric_families = metrics().registry.gather();
+    let mut buffer = Vec::new();
+    let encoder = TextEncoder::new();
+    if encoder.encode(&metric_families, &mut buffer).is_err() {
+        return HttpResponse::InternalServerError().finish();
+    }
+    HttpResponse::Ok()
+        .content_type(encoder.format_type())
+        .body(buffer)
+}
+
+// Register the `/metrics` endpoint.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/metrics", web::get().to(scrape));
+}
@@ -0,0 +1,118 @@
+// BetterGovPH Open Data Visualization - Mobile detection and redirect
+//
+// Replaces the stubbed host-only check so real phones hitting `altgovph.site`
+// are served the `mobile/*.html` templates. By default phones stay on the
+// main host and get `should_use_mobile_template`'s markup; a deployment that
+// also runs a separate `m.` subdomain can opt into redirecting mobile clients
+// there by setting `MOBILE_REDIRECT_ENABLED=1`. A `?desktop=1` override — made
+// sticky with a cookie — lets users escape the mobile view either way.
+
+use actix_web::cookie::Cookie;
+use actix_web::{HttpRequest, HttpResponse};
+
+// Common User-Agent tokens that identify mobile browsers.
+const MOBILE_TOKENS: &[&str] = &[
+    "Android", "iPhone", "iPod", "Mobile", "Opera Mini", "IEMobile", "BlackBerry", "webOS",
+];
+
+// Inspect the `User-Agent` header for a mobile device token.
+pub fn detect_mobile_device(req: &HttpRequest) -> bool {
+    req.headers()
+        .get("user-agent")
+        .and_then(|ua| ua.to_str().ok())
+        .map(|ua| MOBILE_TOKENS.iter().any(|token| ua.contains(token)))
+        .unwrap_or(false)
+}
+
+// True when the user asked to stay on the desktop view, either via `?desktop=1`
+// or the sticky `desktop` cookie set on a previous such request.
+pub fn desktop_override(req: &HttpRequest) -> bool {
+    if let Some(query) = req.uri().query() {
+        if query.split('&').any(|pair| pair == "desktop=1") {
+            return true;
+        }
+    }
+    req.cookie("desktop").map(|c| c.value() == "1").unwrap_or(false)
+}
+
+// Whether the request is already on a mobile host (the `m.`/`mobile.`
+// subdomain).
+fn on_mobile_host(req: &HttpRequest) -> bool {
+    req.headers()
+        .get("host")
+        .and_then(|h| h.to_str().ok())
+        .map(|host| host.starts_with("m.") || host.starts_with("mobile."))
+        .unwrap_or(false)
+}
+
+// Decide whether to render the `mobile/*.html` template: true for mobile hosts
+// or detected mobile devices, unless the desktop override is in effect.
+pub fn should_use_mobile_template(req: &HttpRequest) -> bool {
+    if desktop_override(req) {
+        return false;
+    }
+    on_mobile_host(req) || detect_mobile_device(req)
+}
+
+// Whether the `m.` subdomain redirect is enabled for this deployment. Off by
+// default so a deployment without a separate mobile host keeps serving
+// phones via `should_use_mobile_template` instead of bouncing them to a
+// subdomain that doesn't exist.
+fn redirect_enabled() -> bool {
+    std::env::var("MOBILE_REDIRECT_ENABLED")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+// 302-redirect detected mobile clients to the `m.` subdomain, preserving the
+// path and query string. Returns `None` when no redirect is warranted (already
+// on the mobile host, desktop override active, not a mobile device, or the
+// redirect is disabled for this deployment).
+pub fn check_mobile_redirect_enhanced(req: &HttpRequest) -> Option<HttpResponse> {
+    if !redirect_enabled() || desktop_override(req) || on_mobile_host(req) || !detect_mobile_device(req) {
+        return None;
+    }
+
+    let host = req.headers().get("host")?.to_str().ok()?;
+    let path = req.uri().path();
+    let query = req.uri().query().map(|q| format!("?{q}")).unwrap_or_default();
+    let location = format!("https://m.{host}{path}{query}");
+
+    Some(
+        HttpResponse::Found()
+            .append_header(("Location", location))
+            .finish(),
+    )
+}
+
+// Build the final HTML response, persisting the `?desktop=1` override as a
+// sticky cookie so the user stays on the desktop view on subsequent requests.
+pub fn finalize(req: &HttpRequest, body: String) -> HttpResponse {
+    let mut builder = HttpResponse::Ok();
+    builder.content_type("text/html");
+
+    let asked_for_desktop = req
+        .uri()
+        .query()
+        .map(|q| q.split('&').any(|pair| pair == "desktop=1"))
+        .unwrap_or(false);
+    if asked_for_desktop {
+        let cookie = Cookie::build("desktop", "1").path("/").finish();
+        builder.cookie(cookie);
+    }
+
+    // Persist an explicit `?lang=` override as a sticky cookie so the chosen
+    // locale carries across subsequent requests.
+    if let Some(query) = req.uri().query() {
+        for pair in query.split('&') {
+            if let Some(lang) = pair.strip_prefix("lang=") {
+                if !lang.is_empty() {
+                    let cookie = Cookie::build("lang", lang.to_string()).path("/").finish();
+                    builder.cookie(cookie);
+                }
+            }
+        }
+    }
+
+    builder.body(body)
+}
@@ -0,0 +1,108 @@
+// BetterGovPH Open Data Visualization - Progressive Web App support
+//
+// Serves a versioned service worker and a web app manifest so the visualization
+// pages stay usable on flaky mobile connections. The `sw_version` injected into
+// the Tera context is appended when registering the worker
+// (`sw.js?v={{ sw_version }}`); bumping it forces clients to fetch a fresh
+// worker and drop the stale cache.
+
+use actix_web::{web, HttpResponse};
+
+use crate::templates::StaticAssets;
+
+// Cache-busting version for the service worker. Tied to the crate version so a
+// release automatically invalidates old client caches.
+pub const SW_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// The value injected into templates as `sw_version`.
+pub fn sw_version() -> &'static str {
+    SW_VERSION
+}
+
+// The precache list, derived from whatever is actually in the embedded
+// `static/` store rather than guessed hardcoded paths — a renamed or missing
+// asset just doesn't get precached instead of sinking the whole install.
+fn precache_paths() -> Vec<String> {
+    StaticAssets::iter().map(|path| format!("/static/{path}")).collect()
+}
+
+// The versioned service worker script. It precaches the static assets and keeps
+// the last successfully rendered HTML of the main visualization pages for
+// offline viewing.
+async fn service_worker() -> HttpResponse {
+    let precache = serde_json::to_string(&precache_paths()).unwrap_or_else(|_| "[]".to_string());
+    let script = format!(
+        r#"// BetterGovPH service worker (auto-generated)
+const CACHE = 'altgovph-v{version}';
+const PRECACHE = {precache};
+const PAGES = ['/budget', '/flood', '/dime', '/nep'];
+
+self.addEventListener('install', (event) => {{
+  event.waitUntil(
+    caches.open(CACHE).then((cache) =>
+      Promise.all(PRECACHE.map((url) => cache.add(url).catch(() => {{}})))
+    )
+  );
+  self.skipWaiting();
+}});
+
+self.addEventListener('activate', (event) => {{
+  event.waitUntil(
+    caches.keys().then((keys) =>
+      Promise.all(keys.filter((k) => k !== CACHE).map((k) => caches.delete(k)))
+    )
+  );
+  self.clients.claim();
+}});
+
+self.addEventListener('fetch', (event) => {{
+  const url = new URL(event.request.url);
+  const cacheable = url.pathname.startsWith('/static/') || PAGES.includes(url.pathname);
+  if (!cacheable) return;
+  event.respondWith(
+    fetch(event.request)
+      .then((response) => {{
+        if (response.ok) {{
+          const copy = response.clone();
+          caches.open(CACHE).then((cache) => cache.put(event.request, copy));
+        }}
+        return response;
+      }})
+      .catch(() => caches.match(event.request))
+  );
+}});
+"#,
+        version = SW_VERSION,
+        precache = precache
+    );
+
+    HttpResponse::Ok()
+        .content_type("application/javascript")
+        .body(script)
+}
+
+// The web app manifest.
+async fn manifest() -> HttpResponse {
+    let manifest = serde_json::json!({
+        "name": "BetterGovPH Data Visualizations",
+        "short_name": "BetterGovPH",
+        "start_url": "/",
+        "display": "standalone",
+        "background_color": "#ffffff",
+        "theme_color": "#0038a8",
+        "description": "Open government data visualizations for the Philippines",
+        "icons": [
+            { "src": "/static/images/gov_logo.png", "sizes": "512x512", "type": "image/png" }
+        ]
+    });
+
+    HttpResponse::Ok()
+        .content_type("application/manifest+json")
+        .json(manifest)
+}
+
+// Register the `/sw.js` and `/manifest.webmanifest` routes.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/sw.js", web::get().to(service_worker))
+        .route("/manifest.webmanifest", web::get().to(manifest));
+}
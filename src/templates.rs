@@ -0,0 +1,99 @@
+// BetterGovPH Open Data Visualization - Embedded templates and static assets
+//
+// Templates and static assets are compiled into the binary via `rust_embed` so
+// the server has no working-directory dependency and does no per-request
+// filesystem globbing. The Tera instance is built once at startup (see
+// `build_tera`) and shared through `web::Data<SharedTera>`.
+
+use rust_embed::RustEmbed;
+use std::sync::RwLock;
+use tera::{Context, Tera};
+
+// Embedded copy of the `templates/` directory.
+#[derive(RustEmbed)]
+#[folder = "templates/"]
+pub struct Templates;
+
+// Embedded copy of the `static/` directory.
+#[derive(RustEmbed)]
+#[folder = "static/"]
+pub struct StaticAssets;
+
+// A single embedded template: the name Tera registers it under and the path it
+// lives at inside the embedded store (they are identical for this project, but
+// keeping both makes the registration table explicit).
+struct TemplateFile {
+    name: String,
+    path: String,
+}
+
+// Build the shared Tera instance by registering every embedded template as a
+// raw template. This replaces `Tera::new("templates/**/*")` and its
+// per-request re-globbing.
+pub fn build_tera() -> Result<Tera, tera::Error> {
+    let mut tera = Tera::default();
+
+    let files: Vec<TemplateFile> = Templates::iter()
+        .map(|path| TemplateFile {
+            name: path.to_string(),
+            path: path.to_string(),
+        })
+        .collect();
+
+    for file in &files {
+        if let Some(embedded) = Templates::get(&file.path) {
+            let raw = std::str::from_utf8(embedded.data.as_ref())
+                .map_err(|e| tera::Error::msg(format!("template {} is not valid UTF-8: {e}", file.path)))?;
+            tera.add_raw_template(&file.name, raw)?;
+        }
+    }
+
+    tera.autoescape_on(vec![".html", ".sql"]);
+    Ok(tera)
+}
+
+// Whether a template with the given name is embedded in the binary. Used to
+// fall back from a localized template path to the default when no localized
+// variant exists.
+pub fn template_exists(name: &str) -> bool {
+    Templates::get(name).is_some()
+}
+
+// Shared, pre-compiled Tera instance handed to every handler via `web::Data`.
+//
+// In debug mode (`TERA_DEBUG=1`) each render first calls `full_reload` so edits
+// to the on-disk `templates/` directory are picked up without restarting — the
+// per-request recompile only happens when the developer explicitly opts in, so
+// production still pays zero per-request template cost.
+pub struct SharedTera {
+    inner: RwLock<Tera>,
+    debug: bool,
+}
+
+impl SharedTera {
+    // Build the shared instance, reading the debug flag from the environment.
+    pub fn new() -> Result<SharedTera, tera::Error> {
+        let debug = std::env::var("TERA_DEBUG").map(|v| v == "1").unwrap_or(false);
+        let tera = if debug {
+            // Glob the filesystem so `full_reload` has source files to watch.
+            Tera::new("templates/**/*")?
+        } else {
+            build_tera()?
+        };
+        Ok(SharedTera { inner: RwLock::new(tera), debug })
+    }
+
+    // Render a template against the given context, reloading first in debug mode.
+    pub fn render(&self, name: &str, context: &Context) -> Result<String, tera::Error> {
+        if self.debug {
+            if let Ok(mut tera) = self.inner.write() {
+                tera.full_reload()?;
+            }
+        }
+        let tera = self
+            .inner
+            .read()
+            .map_err(|_| tera::Error::msg("template engine lock poisoned"))?;
+        tera.render(name, context)
+    }
+}
@@ -0,0 +1,114 @@
+// BetterGovPH Open Data Visualization - Admin dataset ingestion
+//
+// Lets maintainers refresh the underlying budget/flood/DIME/NEP files through
+// the running server instead of redeploying. The multipart body is streamed to
+// a temporary file rather than buffered in memory, the header/schema is
+// validated for the target dataset, and only then is the file atomically moved
+// into place and the in-memory aggregation cache invalidated.
+
+use actix_multipart::Multipart;
+use actix_web::{web, HttpRequest, HttpResponse, Result, error::Error as ActixError};
+use futures_util::StreamExt as _;
+use std::io::Write as _;
+
+use crate::data::{self, Dataset};
+
+// Default maximum accepted payload, overridable via `ADMIN_UPLOAD_MAX_BYTES`.
+// Government datasets can be large, so the default is generous (256 MiB).
+const DEFAULT_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+fn max_upload_bytes() -> usize {
+    std::env::var("ADMIN_UPLOAD_MAX_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_BYTES)
+}
+
+// Reject the request unless it carries the configured admin bearer token.
+fn authorized(req: &HttpRequest) -> bool {
+    let expected = match std::env::var("ADMIN_UPLOAD_TOKEN") {
+        Ok(token) if !token.is_empty() => token,
+        _ => return false,
+    };
+    req.headers()
+        .get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(|token| token == expected)
+        .unwrap_or(false)
+}
+
+// Confirm the staged CSV carries every header the dataset requires.
+fn validate_schema(dataset: Dataset, path: &std::path::Path) -> Result<(), String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| format!("could not read uploaded file: {e}"))?;
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("could not read header row: {e}"))?;
+
+    for required in dataset.required_headers() {
+        let present = headers.iter().any(|h| h.eq_ignore_ascii_case(required));
+        if !present {
+            return Err(format!("missing required column `{required}` for dataset"));
+        }
+    }
+    Ok(())
+}
+
+async fn upload(
+    req: HttpRequest,
+    path: web::Path<String>,
+    mut payload: Multipart,
+) -> Result<HttpResponse, ActixError> {
+    if !authorized(&req) {
+        return Ok(HttpResponse::Unauthorized().body("missing or invalid admin token"));
+    }
+
+    let dataset = Dataset::from_slug(&path.into_inner())
+        .ok_or_else(|| actix_web::error::ErrorNotFound("unknown dataset"))?;
+
+    let max_bytes = max_upload_bytes();
+
+    // Stream the first multipart field, whatever its name or type, to a
+    // temporary file in the data directory so
+    // the final rename stays on the same filesystem (atomic).
+    let mut staged = tempfile::NamedTempFile::new_in("data")
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut written = 0usize;
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(actix_web::error::ErrorBadRequest)?;
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(actix_web::error::ErrorBadRequest)?;
+            written += chunk.len();
+            if written > max_bytes {
+                return Ok(HttpResponse::PayloadTooLarge()
+                    .body(format!("upload exceeds {max_bytes} bytes")));
+            }
+            staged
+                .write_all(&chunk)
+                .map_err(actix_web::error::ErrorInternalServerError)?;
+        }
+        break; // only the first field is ingested; later fields are ignored
+    }
+
+    // Validate before publishing; a schema mismatch leaves the live file intact.
+    if let Err(message) = validate_schema(dataset, staged.path()) {
+        return Ok(HttpResponse::BadRequest().body(message));
+    }
+
+    // Atomically move into place and drop the stale cache.
+    staged
+        .persist(dataset.csv_path())
+        .map_err(|e| actix_web::error::ErrorInternalServerError(e.error))?;
+    data::invalidate(dataset);
+
+    Ok(HttpResponse::Ok().body("dataset updated"))
+}
+
+// Register the authenticated admin upload endpoint.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/admin/upload/{dataset}", web::post().to(upload));
+}